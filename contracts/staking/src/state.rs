@@ -32,8 +32,7 @@ pub struct InvestmentInfo {
     pub bond_denom: String,
     /// this is how much the owner takes as a cut when someone unbonds
     pub exit_tax: Decimal,
-    /// All tokens are bonded to this validator
-    /// FIXME: humanize/canonicalize address doesn't work for validator addrresses
+    /// Bonded validator (bech32 HRP `cosmosvaloper`, not `cosmos`)
     pub validator: HumanAddr,
     /// This is the minimum amount we will pull out to reinvest, as well as a minumum
     /// that can be unbonded (to avoid needless staking tx)
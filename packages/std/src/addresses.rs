@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::Deref;
 
+use crate::bech32::{self, Bech32Error};
 use crate::encoding::Binary;
 
 // Added Eq and Hash to allow this to be a key in a HashMap (MockQuerier)
@@ -103,6 +104,20 @@ impl CanonicalAddr {
     pub fn as_slice(&self) -> &[u8] {
         &self.0.as_slice()
     }
+
+    /// Decodes a bech32-encoded [`HumanAddr`] into its canonical (raw) form, checking that it
+    /// was encoded with the given `hrp`. Different address kinds on the same chain can use
+    /// different prefixes, so callers pick the `hrp` that matches what they're decoding.
+    pub fn from_human(human: &HumanAddr, hrp: &str) -> Result<Self, Bech32Error> {
+        bech32::decode(hrp, human.as_str()).map(CanonicalAddr::from)
+    }
+}
+
+impl HumanAddr {
+    /// Encodes raw canonical bytes as a bech32 [`HumanAddr`] under the given `hrp`.
+    pub fn from_canonical(canonical: &CanonicalAddr, hrp: &str) -> Self {
+        HumanAddr(bech32::encode(hrp, canonical.as_slice()))
+    }
 }
 
 impl fmt::Display for CanonicalAddr {
@@ -114,6 +129,72 @@ impl fmt::Display for CanonicalAddr {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum CanonicalAddrError {
+    /// The input had an odd number of hex characters, so it could not be split into bytes.
+    OddLength,
+    /// A character in the input was not a valid hex digit.
+    InvalidHex(char),
+    /// The decoded byte length did not match what the caller expected.
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl fmt::Display for CanonicalAddrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CanonicalAddrError::OddLength => write!(f, "hex string has an odd number of characters"),
+            CanonicalAddrError::InvalidHex(c) => write!(f, "invalid hex character: {:?}", c),
+            CanonicalAddrError::InvalidLength { expected, actual } => write!(
+                f,
+                "invalid address length: expected {} bytes, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CanonicalAddrError {}
+
+impl CanonicalAddr {
+    /// Parses a hex string (either case) into a `CanonicalAddr`. This is the exact inverse
+    /// of [`Display`](std::fmt::Display): `CanonicalAddr::from_hex(&addr.to_string()) == addr`.
+    pub fn from_hex(input: &str) -> Result<Self, CanonicalAddrError> {
+        let chars: Vec<char> = input.chars().collect();
+        if chars.len() % 2 != 0 {
+            return Err(CanonicalAddrError::OddLength);
+        }
+        let mut bytes = Vec::with_capacity(chars.len() / 2);
+        for pair in chars.chunks(2) {
+            let hi = pair[0].to_digit(16).ok_or(CanonicalAddrError::InvalidHex(pair[0]))?;
+            let lo = pair[1].to_digit(16).ok_or(CanonicalAddrError::InvalidHex(pair[1]))?;
+            bytes.push(((hi << 4) | lo) as u8);
+        }
+        Ok(CanonicalAddr::from(bytes))
+    }
+
+    /// Builds a `CanonicalAddr` from `bytes`, asserting it has exactly `expected_len` bytes,
+    /// so contracts can reject malformed addresses instead of silently accepting any length.
+    pub fn try_from_fixed(bytes: Vec<u8>, expected_len: usize) -> Result<Self, CanonicalAddrError> {
+        if bytes.len() != expected_len {
+            return Err(CanonicalAddrError::InvalidLength {
+                expected: expected_len,
+                actual: bytes.len(),
+            });
+        }
+        Ok(CanonicalAddr::from(bytes))
+    }
+
+    /// Generates a random `CanonicalAddr` of `len` bytes. Only available in test builds, as a
+    /// quick way to get distinct, well-formed addresses without hand-writing byte literals.
+    #[cfg(feature = "testing")]
+    pub fn random(len: usize) -> Self {
+        use rand::RngCore;
+        let mut bytes = vec![0u8; len];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        CanonicalAddr::from(bytes)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -266,6 +347,88 @@ mod test {
         assert_eq!(address.to_string(), "1203AB00FF");
     }
 
+    #[test]
+    fn canonical_addr_from_hex_round_trips_with_display() {
+        let cases: Vec<&[u8]> = vec![
+            &[0u8, 187, 61, 11, 250, 0],
+            &[0x00, 0x00, 0x00],
+            &[0xff, 0xff, 0xff],
+            &[],
+            &[0x12, 0x03, 0xab, 0x00, 0xff],
+        ];
+        for bytes in cases {
+            let addr = CanonicalAddr::from(bytes);
+            let round_tripped = CanonicalAddr::from_hex(&addr.to_string()).unwrap();
+            assert_eq!(round_tripped, addr);
+        }
+    }
+
+    #[test]
+    fn canonical_addr_from_hex_accepts_both_cases() {
+        let lower = CanonicalAddr::from_hex("1203ab00ff").unwrap();
+        let upper = CanonicalAddr::from_hex("1203AB00FF").unwrap();
+        assert_eq!(lower, upper);
+        assert_eq!(lower.as_slice(), [0x12, 0x03, 0xab, 0x00, 0xff]);
+    }
+
+    #[test]
+    fn canonical_addr_from_hex_rejects_odd_length() {
+        let err = CanonicalAddr::from_hex("abc").unwrap_err();
+        assert_eq!(err, CanonicalAddrError::OddLength);
+    }
+
+    #[test]
+    fn canonical_addr_from_hex_rejects_non_hex() {
+        let err = CanonicalAddr::from_hex("zz").unwrap_err();
+        assert_eq!(err, CanonicalAddrError::InvalidHex('z'));
+    }
+
+    #[test]
+    fn canonical_addr_try_from_fixed_validates_length() {
+        let addr = CanonicalAddr::try_from_fixed(vec![0u8; 20], 20).unwrap();
+        assert_eq!(addr.as_slice().len(), 20);
+
+        let err = CanonicalAddr::try_from_fixed(vec![0u8; 19], 20).unwrap_err();
+        assert_eq!(
+            err,
+            CanonicalAddrError::InvalidLength {
+                expected: 20,
+                actual: 19
+            }
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn canonical_addr_random_has_requested_length_and_is_distinct() {
+        let a = CanonicalAddr::random(20);
+        let b = CanonicalAddr::random(20);
+        assert_eq!(a.as_slice().len(), 20);
+        assert_eq!(b.as_slice().len(), 20);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn canonical_addr_from_human_and_back() {
+        let human = HumanAddr::from_canonical(&CanonicalAddr::from(vec![0u8; 20]), "cosmos");
+        assert!(human.as_str().starts_with("cosmos1"));
+        let canonical = CanonicalAddr::from_human(&human, "cosmos").unwrap();
+        assert_eq!(canonical.as_slice(), [0u8; 20]);
+    }
+
+    #[test]
+    fn canonical_addr_from_human_rejects_wrong_hrp() {
+        let human = HumanAddr::from_canonical(&CanonicalAddr::from(vec![1u8; 20]), "cosmosvaloper");
+        let err = CanonicalAddr::from_human(&human, "cosmos").unwrap_err();
+        match err {
+            Bech32Error::InvalidHrp { expected, actual } => {
+                assert_eq!(expected, "cosmos");
+                assert_eq!(actual, "cosmosvaloper");
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn canonical_addr_implements_deref() {
         // Dereference to [u8]
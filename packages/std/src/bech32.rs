@@ -0,0 +1,250 @@
+//! A small, dependency-free implementation of the [bech32](https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki)
+//! encoding used by Cosmos SDK addresses (`cosmos1...`, `cosmosvaloper1...`, ...).
+//!
+//! This only implements what we need to humanize/canonicalize [`HumanAddr`](crate::HumanAddr)
+//! and [`CanonicalAddr`](crate::CanonicalAddr) values and does not attempt to be a general
+//! purpose bech32 library (e.g. there is no support for bech32m).
+
+use std::fmt;
+
+const CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const CHECKSUM_LENGTH: usize = 6;
+const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Bech32Error {
+    /// The input did not contain the `1` separator between HRP and data.
+    MissingSeparator,
+    /// The human readable part did not match what was expected.
+    InvalidHrp { expected: String, actual: String },
+    /// A character in the data part is not part of the bech32 charset.
+    InvalidChar(char),
+    /// The input mixes upper and lower case characters, which is not allowed.
+    MixedCase,
+    /// The checksum at the end of the input does not match the computed one.
+    InvalidChecksum,
+    /// The regrouped data did not end on a byte boundary with all-zero padding.
+    InvalidPadding,
+}
+
+impl fmt::Display for Bech32Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bech32Error::MissingSeparator => write!(f, "missing separator \"1\""),
+            Bech32Error::InvalidHrp { expected, actual } => {
+                write!(f, "invalid HRP: expected \"{}\", got \"{}\"", expected, actual)
+            }
+            Bech32Error::InvalidChar(c) => write!(f, "invalid character in data part: {:?}", c),
+            Bech32Error::MixedCase => write!(f, "input mixes upper and lower case"),
+            Bech32Error::InvalidChecksum => write!(f, "invalid checksum"),
+            Bech32Error::InvalidPadding => write!(f, "non-zero padding bits"),
+        }
+    }
+}
+
+impl std::error::Error for Bech32Error {}
+
+/// Computes the bech32 polymod over the given 5-bit values.
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = (chk & 0x1ff_ffff) << 5 ^ u32::from(v);
+        for (i, gen) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+/// Expands the HRP into the high bits, a zero separator and the low bits,
+/// as required as a prefix to the polymod when computing/verifying a checksum.
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(hrp.len() * 2 + 1);
+    out.extend(hrp.bytes().map(|c| c >> 5));
+    out.push(0);
+    out.extend(hrp.bytes().map(|c| c & 31));
+    out
+}
+
+fn create_checksum(hrp: &str, data: &[u8]) -> Vec<u8> {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; CHECKSUM_LENGTH]);
+    let polymod = polymod(&values) ^ 1;
+    (0..CHECKSUM_LENGTH)
+        .map(|i| ((polymod >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 31) as u8)
+        .collect()
+}
+
+fn verify_checksum(hrp: &str, data: &[u8]) -> bool {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    polymod(&values) == 1
+}
+
+/// Regroups `bits_in`-sized groups into `bits_out`-sized groups.
+/// When `pad` is true, a final short group is padded with zero bits;
+/// when false, any leftover bits (fewer than `bits_in`) must all be zero.
+fn convert_bits(data: &[u8], bits_in: u32, bits_out: u32, pad: bool) -> Result<Vec<u8>, Bech32Error> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let maxv: u32 = (1 << bits_out) - 1;
+    let mut out = Vec::with_capacity(data.len() * bits_in as usize / bits_out as usize + 1);
+    for &value in data {
+        acc = (acc << bits_in) | u32::from(value);
+        bits += bits_in;
+        while bits >= bits_out {
+            bits -= bits_out;
+            out.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (bits_out - bits)) & maxv) as u8);
+        }
+    } else if bits >= bits_in || ((acc << (bits_out - bits)) & maxv) != 0 {
+        return Err(Bech32Error::InvalidPadding);
+    }
+    Ok(out)
+}
+
+/// Encodes `data` (arbitrary bytes) with the given human readable part, e.g.
+/// `encode("cosmos", &[0u8; 20])`.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let values = convert_bits(data, 8, 5, true).expect("8 -> 5 bit conversion never fails");
+    let checksum = create_checksum(hrp, &values);
+    let mut out = String::with_capacity(hrp.len() + 1 + values.len() + checksum.len());
+    out.push_str(hrp);
+    out.push('1');
+    for v in values.iter().chain(checksum.iter()) {
+        out.push(CHARSET[*v as usize] as char);
+    }
+    out
+}
+
+/// Decodes a bech32 string, checking that its human readable part matches `expected_hrp`
+/// and that the checksum is valid. Returns the decoded data bytes.
+pub fn decode(expected_hrp: &str, input: &str) -> Result<Vec<u8>, Bech32Error> {
+    let has_lower = input.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = input.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Bech32Error::MixedCase);
+    }
+    let input_lower = input.to_ascii_lowercase();
+
+    let separator = input_lower
+        .rfind('1')
+        .ok_or(Bech32Error::MissingSeparator)?;
+    let (hrp, data_part) = input_lower.split_at(separator);
+    let data_part = &data_part[1..];
+
+    if hrp != expected_hrp {
+        return Err(Bech32Error::InvalidHrp {
+            expected: expected_hrp.to_string(),
+            actual: hrp.to_string(),
+        });
+    }
+
+    if data_part.len() < CHECKSUM_LENGTH {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+
+    let mut values = Vec::with_capacity(data_part.len());
+    for c in data_part.chars() {
+        let v = CHARSET
+            .iter()
+            .position(|&x| x as char == c)
+            .ok_or(Bech32Error::InvalidChar(c))?;
+        values.push(v as u8);
+    }
+
+    if !verify_checksum(hrp, &values) {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+
+    let data = &values[..values.len() - CHECKSUM_LENGTH];
+    convert_bits(data, 5, 8, false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let data = vec![0u8, 1, 2, 3, 255, 254, 16, 17];
+        let encoded = encode("cosmos", &data);
+        assert!(encoded.starts_with("cosmos1"));
+        let decoded = decode("cosmos", &encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn encode_decode_empty_data() {
+        let encoded = encode("cosmos", &[]);
+        let decoded = decode("cosmos", &encoded).unwrap();
+        assert_eq!(decoded, Vec::<u8>::new());
+    }
+
+    #[test]
+    fn encode_decode_single_0xff_byte() {
+        // 0xFF alone regroups into 5-bit values that include 31, the top of the charset; this
+        // would panic on an incomplete (31-symbol) charset.
+        let data = vec![0xffu8];
+        let encoded = encode("cosmos", &data);
+        let decoded = decode("cosmos", &encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn encode_decode_all_byte_values() {
+        let data: Vec<u8> = (0..=255).collect();
+        let encoded = encode("cosmos", &data);
+        let decoded = decode("cosmos", &encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn decode_rejects_wrong_hrp() {
+        let encoded = encode("cosmos", &[1, 2, 3]);
+        let err = decode("cosmosvaloper", &encoded).unwrap_err();
+        assert_eq!(
+            err,
+            Bech32Error::InvalidHrp {
+                expected: "cosmosvaloper".to_string(),
+                actual: "cosmos".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn decode_rejects_bad_checksum() {
+        let mut encoded = encode("cosmos", &[1, 2, 3]);
+        // flip the last checksum character
+        let last = encoded.pop().unwrap();
+        let replacement = if last == 'q' { 'p' } else { 'q' };
+        encoded.push(replacement);
+        assert_eq!(decode("cosmos", &encoded), Err(Bech32Error::InvalidChecksum));
+    }
+
+    #[test]
+    fn decode_rejects_mixed_case() {
+        let encoded = encode("cosmos", &[1, 2, 3]);
+        let mut chars: Vec<char> = encoded.chars().collect();
+        let idx = chars.len() - 1;
+        chars[idx] = chars[idx].to_ascii_uppercase();
+        let mixed: String = chars.into_iter().collect();
+        assert_eq!(decode("cosmos", &mixed), Err(Bech32Error::MixedCase));
+    }
+
+    #[test]
+    fn decode_rejects_missing_separator() {
+        assert_eq!(
+            decode("cosmos", "nooneseparator"),
+            Err(Bech32Error::MissingSeparator)
+        );
+    }
+}
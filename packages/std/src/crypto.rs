@@ -0,0 +1,313 @@
+//! secp256k1 signature verification and public key recovery, exposed to contracts through
+//! [`Secp256k1Api`] so that callers never link the underlying crypto crate directly.
+
+use std::fmt;
+
+use crate::addresses::CanonicalAddr;
+
+/// The length, in bytes, of a secp256k1 message hash (e.g. produced by SHA-256).
+pub const MESSAGE_HASH_LENGTH: usize = 32;
+/// The length, in bytes, of a compact (r, s) secp256k1 signature.
+pub const SIGNATURE_LENGTH: usize = 64;
+/// The length, in bytes, of a truncated canonical address derived from a public key.
+pub const CANONICAL_ADDRESS_LENGTH: usize = 20;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CryptoError {
+    /// `message_hash` was not exactly [`MESSAGE_HASH_LENGTH`] bytes long.
+    InvalidHashFormat,
+    /// `signature` was not exactly [`SIGNATURE_LENGTH`] bytes long, or was not a valid (r, s) pair.
+    InvalidSignatureFormat,
+    /// `pubkey` was not a valid SEC1-encoded secp256k1 public key.
+    InvalidPubkeyFormat,
+    /// `recovery_id` was not 0 or 1.
+    InvalidRecoveryParam,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CryptoError::InvalidHashFormat => write!(
+                f,
+                "message hash must be exactly {} bytes",
+                MESSAGE_HASH_LENGTH
+            ),
+            CryptoError::InvalidSignatureFormat => write!(
+                f,
+                "signature must be a valid {}-byte compact (r, s) pair",
+                SIGNATURE_LENGTH
+            ),
+            CryptoError::InvalidPubkeyFormat => write!(f, "public key is not a valid SEC1-encoded point"),
+            CryptoError::InvalidRecoveryParam => write!(f, "recovery id must be 0 or 1"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// The contract-facing crypto surface. Methods take `&self` (rather than being free
+/// functions) so the gas-metered host boundary stays explicit at every call site, the same
+/// way address (de)serialization is reached through `Api` rather than called as a bare
+/// function.
+pub trait Secp256k1Api {
+    /// Verifies a secp256k1 signature over `message_hash` for the given `pubkey`.
+    ///
+    /// `message_hash` must be a 32-byte hash (e.g. SHA-256) of the signed message, `signature`
+    /// must be a 64-byte compact `(r, s)` signature, and `pubkey` a SEC1-encoded (compressed or
+    /// uncompressed) public key. Returns `Ok(false)` -- not an error -- when the signature is
+    /// well-formed but does not match, so callers can branch on the result without a fallible
+    /// match against malformed input.
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> Result<bool, CryptoError>;
+
+    /// Recovers the SEC1-encoded (uncompressed) public key that produced `signature` over
+    /// `message_hash`, given the 0/1 `recovery_id` returned alongside the signature at signing
+    /// time.
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_id: u8,
+    ) -> Result<Vec<u8>, CryptoError>;
+}
+
+fn checked_message(message_hash: &[u8]) -> Result<secp256k1::Message, CryptoError> {
+    if message_hash.len() != MESSAGE_HASH_LENGTH {
+        return Err(CryptoError::InvalidHashFormat);
+    }
+    secp256k1::Message::from_slice(message_hash).map_err(|_| CryptoError::InvalidHashFormat)
+}
+
+fn verify(message_hash: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<bool, CryptoError> {
+    if signature.len() != SIGNATURE_LENGTH {
+        return Err(CryptoError::InvalidSignatureFormat);
+    }
+
+    let message = checked_message(message_hash)?;
+    let signature = secp256k1::ecdsa::Signature::from_compact(signature)
+        .map_err(|_| CryptoError::InvalidSignatureFormat)?;
+    let pubkey =
+        secp256k1::PublicKey::from_slice(pubkey).map_err(|_| CryptoError::InvalidPubkeyFormat)?;
+
+    let secp = secp256k1::Secp256k1::verification_only();
+    Ok(secp.verify_ecdsa(&message, &signature, &pubkey).is_ok())
+}
+
+fn recover_pubkey(
+    message_hash: &[u8],
+    signature: &[u8],
+    recovery_id: u8,
+) -> Result<Vec<u8>, CryptoError> {
+    if signature.len() != SIGNATURE_LENGTH {
+        return Err(CryptoError::InvalidSignatureFormat);
+    }
+
+    let message = checked_message(message_hash)?;
+    if recovery_id > 1 {
+        return Err(CryptoError::InvalidRecoveryParam);
+    }
+    let recovery_id = secp256k1::ecdsa::RecoveryId::from_i32(i32::from(recovery_id))
+        .map_err(|_| CryptoError::InvalidRecoveryParam)?;
+    let recoverable_signature =
+        secp256k1::ecdsa::RecoverableSignature::from_compact(signature, recovery_id)
+            .map_err(|_| CryptoError::InvalidSignatureFormat)?;
+
+    let secp = secp256k1::Secp256k1::verification_only();
+    let pubkey = secp
+        .recover_ecdsa(&message, &recoverable_signature)
+        .map_err(|_| CryptoError::InvalidSignatureFormat)?;
+    Ok(pubkey.serialize_uncompressed().to_vec())
+}
+
+/// The production `Secp256k1Api` implementation, backed directly by the `secp256k1` crate.
+pub struct ExternalApi;
+
+impl Secp256k1Api for ExternalApi {
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> Result<bool, CryptoError> {
+        verify(message_hash, signature, pubkey)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_id: u8,
+    ) -> Result<Vec<u8>, CryptoError> {
+        recover_pubkey(message_hash, signature, recovery_id)
+    }
+}
+
+/// A deterministic `Secp256k1Api` for contract test suites. It runs the same math as
+/// [`ExternalApi`] (there being no meaningful way to fake ECDSA verification), it just lets
+/// tests reach for it without depending on a host-provided implementation.
+#[cfg(feature = "testing")]
+pub struct MockApi;
+
+#[cfg(feature = "testing")]
+impl Secp256k1Api for MockApi {
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> Result<bool, CryptoError> {
+        verify(message_hash, signature, pubkey)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_id: u8,
+    ) -> Result<Vec<u8>, CryptoError> {
+        recover_pubkey(message_hash, signature, recovery_id)
+    }
+}
+
+/// Derives a [`CanonicalAddr`] from a secp256k1 public key (SEC1-encoded, compressed or
+/// uncompressed -- e.g. straight from [`Secp256k1Api::secp256k1_recover_pubkey`]) the same way
+/// the Cosmos SDK does: `RIPEMD160(SHA-256(compressed_pubkey))`, truncated to
+/// [`CANONICAL_ADDRESS_LENGTH`] bytes. The key is re-serialized to its compressed form first so
+/// the result does not depend on which encoding the caller happened to pass in.
+pub fn addr_from_pubkey(pubkey: &[u8]) -> Result<CanonicalAddr, CryptoError> {
+    use ripemd160::{Digest as _, Ripemd160};
+    use sha2::Sha256;
+
+    let pubkey = secp256k1::PublicKey::from_slice(pubkey).map_err(|_| CryptoError::InvalidPubkeyFormat)?;
+    let sha256_digest = Sha256::digest(pubkey.serialize());
+    let ripemd_digest = Ripemd160::digest(&sha256_digest);
+    Ok(CanonicalAddr::from(ripemd_digest[..CANONICAL_ADDRESS_LENGTH].to_vec()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sign(message_hash: &[u8; 32], secret_key: &secp256k1::SecretKey) -> ([u8; 64], u8) {
+        let secp = secp256k1::Secp256k1::signing_only();
+        let message = secp256k1::Message::from_slice(message_hash).unwrap();
+        let (recovery_id, signature) = secp
+            .sign_ecdsa_recoverable(&message, secret_key)
+            .serialize_compact();
+        (signature, recovery_id.to_i32() as u8)
+    }
+
+    #[test]
+    fn verify_accepts_valid_signature() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+        let message_hash = [42u8; 32];
+        let (signature, _recovery_id) = sign(&message_hash, &secret_key);
+
+        let valid = ExternalApi
+            .secp256k1_verify(&message_hash, &signature, &pubkey.serialize_uncompressed())
+            .unwrap();
+        assert!(valid);
+    }
+
+    #[test]
+    fn verify_rejects_non_matching_signature_without_erroring() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+        let message_hash = [42u8; 32];
+        let (signature, _recovery_id) = sign(&[0u8; 32], &secret_key);
+
+        let valid = ExternalApi
+            .secp256k1_verify(&message_hash, &signature, &pubkey.serialize_uncompressed())
+            .unwrap();
+        assert!(!valid);
+    }
+
+    #[test]
+    fn verify_rejects_malformed_input_with_typed_error() {
+        let err = ExternalApi
+            .secp256k1_verify(&[0u8; 31], &[0u8; 64], &[0u8; 33])
+            .unwrap_err();
+        assert_eq!(err, CryptoError::InvalidHashFormat);
+
+        let err = ExternalApi
+            .secp256k1_verify(&[0u8; 32], &[0u8; 63], &[0u8; 33])
+            .unwrap_err();
+        assert_eq!(err, CryptoError::InvalidSignatureFormat);
+    }
+
+    #[test]
+    fn recover_pubkey_round_trips_with_sign() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+        let message_hash = [42u8; 32];
+        let (signature, recovery_id) = sign(&message_hash, &secret_key);
+
+        let recovered = ExternalApi
+            .secp256k1_recover_pubkey(&message_hash, &signature, recovery_id)
+            .unwrap();
+        assert_eq!(recovered, pubkey.serialize_uncompressed().to_vec());
+    }
+
+    #[test]
+    fn recover_pubkey_rejects_bad_recovery_param() {
+        let err = ExternalApi
+            .secp256k1_recover_pubkey(&[0u8; 32], &[0u8; 64], 5)
+            .unwrap_err();
+        assert_eq!(err, CryptoError::InvalidRecoveryParam);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn mock_api_agrees_with_external_api() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+        let message_hash = [42u8; 32];
+        let (signature, recovery_id) = sign(&message_hash, &secret_key);
+
+        let mock_valid = MockApi
+            .secp256k1_verify(&message_hash, &signature, &pubkey.serialize_uncompressed())
+            .unwrap();
+        assert!(mock_valid);
+
+        let mock_recovered = MockApi
+            .secp256k1_recover_pubkey(&message_hash, &signature, recovery_id)
+            .unwrap();
+        assert_eq!(mock_recovered, pubkey.serialize_uncompressed().to_vec());
+    }
+
+    #[test]
+    fn addr_from_pubkey_has_canonical_length() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+
+        let addr = addr_from_pubkey(&pubkey.serialize_uncompressed()).unwrap();
+        assert_eq!(addr.as_slice().len(), CANONICAL_ADDRESS_LENGTH);
+    }
+
+    #[test]
+    fn addr_from_pubkey_is_independent_of_compression() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+
+        let from_compressed = addr_from_pubkey(&pubkey.serialize()).unwrap();
+        let from_uncompressed = addr_from_pubkey(&pubkey.serialize_uncompressed()).unwrap();
+        assert_eq!(from_compressed, from_uncompressed);
+    }
+
+    #[test]
+    fn addr_from_pubkey_rejects_malformed_pubkey() {
+        let err = addr_from_pubkey(&[0u8; 33]).unwrap_err();
+        assert_eq!(err, CryptoError::InvalidPubkeyFormat);
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::Storage;
+#[cfg(feature = "iterator")]
+use cosmwasm_std::{Order, KV};
+
+/// Wraps `storage` in a buffered overlay: `set`/`remove` calls are staged in memory and only
+/// applied to the backing storage once [`commit`](TransactionalStorage::commit) is called.
+/// [`rollback`](TransactionalStorage::rollback) discards the overlay, leaving `storage` untouched.
+///
+/// Useful whenever a multi-step write should be all-or-nothing: stage the writes in a
+/// `TransactionalStorage`, and only `commit` once every step has succeeded.
+///
+/// Because `TransactionalStorage` itself implements [`Storage`], it composes with
+/// [`PrefixedStorage`](crate::PrefixedStorage) (in either direction) and with itself, which is
+/// how nested savepoints are modeled: [`begin`](TransactionalStorage::begin) opens a new overlay
+/// on top of the current one, and its own `commit`/`rollback` only affect that nested layer.
+pub struct TransactionalStorage<'a, S>
+where
+    S: Storage,
+{
+    storage: &'a mut S,
+    overlay: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a, S> TransactionalStorage<'a, S>
+where
+    S: Storage,
+{
+    pub fn new(storage: &'a mut S) -> Self {
+        TransactionalStorage {
+            storage,
+            overlay: BTreeMap::new(),
+        }
+    }
+
+    /// Opens a nested savepoint on top of this transaction. The returned
+    /// `TransactionalStorage` can be committed or rolled back independently: committing
+    /// flushes its overlay into `self` (still staged, not yet visible outside `self`);
+    /// rolling back drops it without touching `self` at all.
+    pub fn begin(&mut self) -> TransactionalStorage<'_, Self> {
+        TransactionalStorage::new(self)
+    }
+
+    /// Flushes the staged `set`/`remove` calls into the backing storage, in key order.
+    pub fn commit(self) {
+        let TransactionalStorage { storage, overlay } = self;
+        for (key, value) in overlay {
+            match value {
+                Some(value) => storage.set(&key, &value),
+                None => storage.remove(&key),
+            }
+        }
+    }
+
+    /// Discards the staged `set`/`remove` calls. The backing storage is left untouched.
+    pub fn rollback(self) {
+        // Dropping `self.overlay` is enough; spelled out for clarity at call sites.
+        drop(self);
+    }
+}
+
+impl<'a, S> Storage for TransactionalStorage<'a, S>
+where
+    S: Storage,
+{
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.overlay.get(key) {
+            Some(Some(value)) => Some(value.clone()),
+            Some(None) => None,
+            None => self.storage.get(key),
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.overlay.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.overlay.insert(key.to_vec(), None);
+    }
+
+    #[cfg(feature = "iterator")]
+    /// range allows iteration over a set of keys, either forwards or backwards, merging the
+    /// sorted in-memory overlay with the backing range and applying pending deletions.
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = KV> + 'b> {
+        let bounds = (
+            start.map_or(std::ops::Bound::Unbounded, std::ops::Bound::Included),
+            end.map_or(std::ops::Bound::Unbounded, std::ops::Bound::Excluded),
+        );
+        let overlay: Vec<(Vec<u8>, Option<Vec<u8>>)> = if order == Order::Descending {
+            self.overlay
+                .range::<[u8], _>(bounds)
+                .rev()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        } else {
+            self.overlay
+                .range::<[u8], _>(bounds)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        };
+
+        let backing = self.storage.range(start, end, order);
+
+        Box::new(MergedRange {
+            overlay: overlay.into_iter().peekable(),
+            backing: backing.peekable(),
+            order,
+        })
+    }
+}
+
+#[cfg(feature = "iterator")]
+struct MergedRange<'b> {
+    overlay: std::iter::Peekable<std::vec::IntoIter<(Vec<u8>, Option<Vec<u8>>)>>,
+    backing: std::iter::Peekable<Box<dyn Iterator<Item = KV> + 'b>>,
+    order: Order,
+}
+
+#[cfg(feature = "iterator")]
+impl<'b> Iterator for MergedRange<'b> {
+    type Item = KV;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let pick_overlay = match (self.overlay.peek(), self.backing.peek()) {
+                (Some((ok, _)), Some((bk, _))) => match self.order {
+                    Order::Ascending => ok <= bk,
+                    Order::Descending => ok >= bk,
+                },
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return None,
+            };
+
+            if pick_overlay {
+                let (key, value) = self.overlay.next().unwrap();
+                // an overlay entry always wins over a backing entry with the same key
+                if let Some((bk, _)) = self.backing.peek() {
+                    if *bk == key {
+                        self.backing.next();
+                    }
+                }
+                if let Some(value) = value {
+                    return Some((key, value));
+                }
+                // tombstone: skip and keep looking
+            } else {
+                return self.backing.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn get_checks_overlay_before_backing_storage() {
+        let mut backing = MockStorage::new();
+        backing.set(b"foo", b"bar");
+
+        let mut tx = TransactionalStorage::new(&mut backing);
+        assert_eq!(tx.get(b"foo"), Some(b"bar".to_vec()));
+
+        tx.set(b"foo", b"staged");
+        assert_eq!(tx.get(b"foo"), Some(b"staged".to_vec()));
+
+        tx.remove(b"foo");
+        assert_eq!(tx.get(b"foo"), None);
+    }
+
+    #[test]
+    fn commit_flushes_overlay_into_backing_storage() {
+        let mut backing = MockStorage::new();
+        backing.set(b"a", b"1");
+
+        let mut tx = TransactionalStorage::new(&mut backing);
+        tx.set(b"b", b"2");
+        tx.remove(b"a");
+        tx.commit();
+
+        assert_eq!(backing.get(b"a"), None);
+        assert_eq!(backing.get(b"b"), Some(b"2".to_vec()));
+    }
+
+    #[test]
+    fn rollback_leaves_backing_storage_untouched() {
+        let mut backing = MockStorage::new();
+        backing.set(b"a", b"1");
+
+        let mut tx = TransactionalStorage::new(&mut backing);
+        tx.set(b"a", b"overwritten");
+        tx.remove(b"a");
+        tx.set(b"b", b"2");
+        tx.rollback();
+
+        assert_eq!(backing.get(b"a"), Some(b"1".to_vec()));
+        assert_eq!(backing.get(b"b"), None);
+    }
+
+    #[test]
+    fn delete_then_reset_in_same_transaction_keeps_final_value() {
+        let mut backing = MockStorage::new();
+        backing.set(b"a", b"1");
+
+        let mut tx = TransactionalStorage::new(&mut backing);
+        tx.remove(b"a");
+        tx.set(b"a", b"2");
+        assert_eq!(tx.get(b"a"), Some(b"2".to_vec()));
+        tx.commit();
+
+        assert_eq!(backing.get(b"a"), Some(b"2".to_vec()));
+    }
+
+    #[test]
+    fn nested_savepoint_rollback_does_not_affect_outer_transaction() {
+        let mut backing = MockStorage::new();
+
+        let mut outer = TransactionalStorage::new(&mut backing);
+        outer.set(b"a", b"outer");
+
+        {
+            let mut inner = outer.begin();
+            inner.set(b"a", b"inner");
+            inner.set(b"b", b"inner-only");
+            inner.rollback();
+        }
+
+        assert_eq!(outer.get(b"a"), Some(b"outer".to_vec()));
+        assert_eq!(outer.get(b"b"), None);
+
+        outer.commit();
+        assert_eq!(backing.get(b"a"), Some(b"outer".to_vec()));
+        assert_eq!(backing.get(b"b"), None);
+    }
+
+    #[test]
+    fn nested_savepoint_commit_only_reaches_outer_transaction() {
+        let mut backing = MockStorage::new();
+
+        let mut outer = TransactionalStorage::new(&mut backing);
+        {
+            let mut inner = outer.begin();
+            inner.set(b"a", b"inner");
+            inner.commit();
+        }
+        // the nested commit only staged the write on `outer`; the backing storage is untouched
+        assert_eq!(outer.get(b"a"), Some(b"inner".to_vec()));
+        assert_eq!(outer.storage.get(b"a"), None);
+
+        outer.commit();
+        assert_eq!(backing.get(b"a"), Some(b"inner".to_vec()));
+    }
+
+    #[cfg(feature = "iterator")]
+    #[test]
+    fn range_merges_overlay_and_backing_in_order() {
+        let mut backing = MockStorage::new();
+        backing.set(b"a", b"1");
+        backing.set(b"c", b"3");
+        backing.set(b"d", b"4");
+
+        let mut tx = TransactionalStorage::new(&mut backing);
+        tx.set(b"b", b"2-staged");
+        tx.remove(b"c");
+        tx.set(b"d", b"4-staged");
+
+        let items: Vec<KV> = tx.range(None, None, Order::Ascending).collect();
+        assert_eq!(
+            items,
+            vec![
+                (b"a".to_vec(), b"1".to_vec()),
+                (b"b".to_vec(), b"2-staged".to_vec()),
+                (b"d".to_vec(), b"4-staged".to_vec()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "iterator")]
+    #[test]
+    fn range_honors_descending_order_and_bounds() {
+        let mut backing = MockStorage::new();
+        backing.set(b"a", b"1");
+        backing.set(b"b", b"2");
+        backing.set(b"d", b"4");
+
+        let mut tx = TransactionalStorage::new(&mut backing);
+        tx.set(b"c", b"3-staged");
+
+        let items: Vec<KV> = tx
+            .range(Some(b"b"), Some(b"d"), Order::Descending)
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                (b"c".to_vec(), b"3-staged".to_vec()),
+                (b"b".to_vec(), b"2".to_vec()),
+            ]
+        );
+    }
+}